@@ -10,36 +10,85 @@
 //!
 //!   # Or specify values directly
 //!   verify_ed25519ph --pubkey developer --hash <hex> --sig <hex> --name boot1
+//!
+//!   # Generate a fresh test keypair
+//!   verify_ed25519ph keygen
+//!
+//!   # Sign an image and emit an audit-style block
+//!   verify_ed25519ph sign --stage boot1 --key <hex seed> --image boot1.bin
+//!
+//!   # Force the algorithm for an ECDSA-backed attestation key
+//!   verify_ed25519ph --pubkey <33-byte hex> --hash <hex> --sig <hex> --alg secp256k1
+//!
+//!   # Emit and verify an ASCII-armored block instead of loose hex fields
+//!   # (--key-tag names the trust-store/PUBKEYS entry to verify against -
+//!   # the block itself never carries a trusted public key)
+//!   verify_ed25519ph sign --stage boot1 --key <hex seed> --image boot1.bin --armor --key-tag developer > boot1.asc
+//!   cat boot1.asc | verify_ed25519ph
 
-use clap::Parser;
-use ed25519_dalek::{Signature, VerifyingKey};
+use clap::{Parser, Subcommand};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 use sha2::digest::{FixedOutput, HashMarker, Output, OutputSizeUser, Reset, Update};
+use sha2::{Digest, Sha256, Sha512};
 use std::collections::HashMap;
 use std::io::{self, BufRead};
+use std::path::PathBuf;
+
+/// Attestation signature algorithm. Ed25519 is the default for all built-in
+/// slots; secp256k1/ECDSA is for Baochip parts backed by an ECDSA-capable HSM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Algorithm {
+    Ed25519,
+    Secp256k1,
+}
+
+impl Algorithm {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "ed25519" => Ok(Algorithm::Ed25519),
+            "secp256k1" | "ecdsa" => Ok(Algorithm::Secp256k1),
+            other => Err(format!("Unknown algorithm '{}' (expected ed25519 or secp256k1)", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Algorithm::Ed25519 => write!(f, "ed25519"),
+            Algorithm::Secp256k1 => write!(f, "secp256k1"),
+        }
+    }
+}
 
 /// Known public keys from libs/bao1x-api/src/pubkeys/
 /// Key slots: 0=bao1, 1=bao2, 2=beta, 3=developer
-const PUBKEYS: &[(&str, &str)] = &[
+const PUBKEYS: &[(&str, &str, Algorithm)] = &[
     (
         "bao1",
         "a87a5f98daabfb512fc3c2e5749b3beb192388d20160a7dd5888fb9da409523a",
+        Algorithm::Ed25519,
     ),
     (
         "bao2",
         "79135dc667aff4f7d352b90328788ebf92c7867821388b77370b15194e312888",
+        Algorithm::Ed25519,
     ),
     (
         "beta",
         "80979929edd04e40124b52cae9ae54b24bdff72a7b8a004c41065bd1402078a7",
+        Algorithm::Ed25519,
     ),
     (
         "developer",
         "1c9beae32aeac87507c18094387eff1c74614282affd8152d871352edf3f58bb",
+        Algorithm::Ed25519,
     ),
     // Aliases
     (
         "dev",
         "1c9beae32aeac87507c18094387eff1c74614282affd8152d871352edf3f58bb",
+        Algorithm::Ed25519,
     ),
 ];
 
@@ -64,6 +113,9 @@ const TAG_TO_KEY: &[(&str, &str)] = &[
     verify_ed25519ph --pubkey developer --hash <hex> --sig <hex>"
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// Public key in hex (32 bytes) or key name (bao1, bao2, beta, developer)
     #[arg(short, long)]
     pubkey: Option<String>,
@@ -83,6 +135,216 @@ struct Args {
     /// Name/stage to verify (boot0, boot1, loader) - used when parsing audit output
     #[arg(short, long, default_value = "all")]
     name: String,
+
+    /// Force the verification algorithm (ed25519, secp256k1) instead of auto-detecting it
+    #[arg(long)]
+    alg: Option<String>,
+
+    /// Path to a JSON trust store (keys indexed by slot/name, with algorithm,
+    /// revocation and expiry) consulted before the built-in key table
+    #[arg(long, value_name = "file")]
+    trust_store: Option<PathBuf>,
+}
+
+/// One key entry in an external `--trust-store` file.
+#[derive(serde::Deserialize)]
+struct TrustStoreEntry {
+    /// Key slot number (0=bao1, 1=bao2, 2=beta, 3=developer by convention)
+    slot: Option<u32>,
+    /// Key name, matched against `--pubkey` and the audit `key_tag`
+    name: String,
+    /// 4-byte audit tag for this key, if different from `name`
+    tag: Option<String>,
+    /// Public key in hex (32 bytes for Ed25519, 33-byte compressed for secp256k1)
+    pubkey: String,
+    /// Signature algorithm; defaults to Ed25519 if omitted
+    alg: Option<String>,
+    /// If true, this key is retired and must fail verification regardless of the signature
+    #[serde(default)]
+    revoked: bool,
+    /// Validity cutoff as `YYYY-MM-DD`; verification fails if this date has passed
+    not_after: Option<String>,
+}
+
+/// An external key/slot/revocation trust store loaded via `--trust-store`.
+#[derive(serde::Deserialize, Default)]
+struct TrustStore {
+    #[serde(default)]
+    keys: Vec<TrustStoreEntry>,
+}
+
+fn load_trust_store(path: &std::path::Path) -> Result<TrustStore, String> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read trust store {:?}: {}", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Invalid trust store {:?}: {}", path, e))
+}
+
+/// Validity state of a resolved key, independent of whether its signature checks out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyStatus {
+    Valid,
+    Revoked,
+    Expired,
+}
+
+/// Errors rather than silently treating a malformed `not_after` as "no expiry
+/// set" - a trust-store authoring typo must not quietly disable expiry
+/// enforcement.
+fn key_status(entry: &TrustStoreEntry) -> Result<KeyStatus, String> {
+    if entry.revoked {
+        return Ok(KeyStatus::Revoked);
+    }
+    if let Some(ref not_after) = entry.not_after {
+        let expiry = chrono::NaiveDate::parse_from_str(not_after, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid not_after date '{}' for key '{}': {}", not_after, entry.name, e))?;
+        if chrono::Utc::now().date_naive() > expiry {
+            return Ok(KeyStatus::Expired);
+        }
+    }
+    Ok(KeyStatus::Valid)
+}
+
+/// A public key resolved from the trust store (preferred) or the built-in
+/// `PUBKEYS` table, along with its algorithm, display name, and status.
+struct ResolvedKey {
+    bytes: Vec<u8>,
+    alg: Option<Algorithm>,
+    display_name: Option<String>,
+    status: KeyStatus,
+}
+
+/// Companion key-management subcommands, mirroring the usual
+/// keygen -> sign -> verify flow so test vectors don't have to be
+/// hand-crafted.
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate a fresh Ed25519 signing key and print the hex seed + pubkey
+    Keygen,
+    /// Sign an image and emit a ready-to-paste `<stage>.sig:`/`<stage>.hash:` block
+    Sign {
+        /// Stage name to use in the emitted audit block (boot0, boot1, loader)
+        #[arg(long, default_value = "boot1")]
+        stage: String,
+
+        /// Signing key seed in hex (32 bytes), as produced by `keygen`
+        #[arg(long)]
+        key: String,
+
+        /// Path to the image region to sign
+        #[arg(long)]
+        image: PathBuf,
+
+        /// AAD in hex - if provided, emits the FIDO2 variant instead of Ed25519ph
+        #[arg(long)]
+        aad: Option<String>,
+
+        /// Emit a self-describing, checksummed armored block instead of raw sig/hash lines
+        #[arg(long)]
+        armor: bool,
+
+        /// Key name/tag this signing key corresponds to (e.g. "developer", "beta"),
+        /// resolved the same way as `key_tag` in the plain audit format - required
+        /// with --armor, since the armored block is never trusted to carry its own
+        /// verification key
+        #[arg(long)]
+        key_tag: Option<String>,
+    },
+}
+
+/// Generate a fresh `SigningKey`/`VerifyingKey` pair and print the hex seed + pubkey.
+fn keygen() {
+    let mut csprng = rand_core::OsRng;
+    let signing_key = SigningKey::generate(&mut csprng);
+    println!("seed:   {}", hex::encode(signing_key.to_bytes()));
+    println!("pubkey: {}", hex::encode(signing_key.verifying_key().to_bytes()));
+}
+
+/// Result of signing an image: the hash/signature (and AAD, if used) ready to
+/// be printed as audit lines or wrapped in an armored block.
+struct SignedStage {
+    hash_hex: String,
+    sig_hex: String,
+    aad_hex: Option<String>,
+}
+
+/// Sign `image` with `signing_key`, producing the hash/signature pair the same
+/// way `verify_single` checks them: without `aad_hex` this is an Ed25519ph
+/// signature over the SHA-512 prehash of the image; with `aad_hex` it's the
+/// FIDO2 variant (standard Ed25519 over `aad || SHA256(SHA512(image))`).
+fn sign_image(signing_key: &SigningKey, image: &PathBuf, aad_hex: Option<&str>) -> Result<SignedStage, String> {
+    let image_bytes = std::fs::read(image).map_err(|e| format!("Failed to read {:?}: {}", image, e))?;
+
+    let mut hasher = Sha512::new();
+    Digest::update(&mut hasher, &image_bytes);
+    let hash_bytes: [u8; 64] = hasher.finalize().into();
+
+    let aad_bytes: Option<Vec<u8>> = match aad_hex {
+        Some(hex) if !hex.is_empty() => {
+            Some(hex::decode(hex).map_err(|e| format!("Invalid AAD hex: {}", e))?)
+        }
+        _ => None,
+    };
+
+    let signature = if let Some(ref aad) = aad_bytes {
+        let msg = fido2_message(&hash_bytes, aad);
+        signing_key.sign(&msg)
+    } else {
+        let prehash = Sha512::new_with_prefix(&image_bytes);
+        signing_key
+            .sign_prehashed(prehash, None)
+            .map_err(|e| format!("Signing failed: {}", e))?
+    };
+
+    Ok(SignedStage {
+        hash_hex: hex::encode(hash_bytes),
+        sig_hex: hex::encode(signature.to_bytes()),
+        aad_hex: aad_bytes.as_ref().map(hex::encode),
+    })
+}
+
+/// Sign `image` with `key_hex` for `stage` and print an audit-style sig/hash block
+/// (or, with `armor`, an ASCII-armored `BAOCHIP ATTESTATION` block). `key_tag`
+/// is required with `armor`: the armored block carries that tag rather than
+/// the raw public key, so verification still resolves the key through the
+/// trust store / built-in table instead of trusting whatever key signed it.
+fn sign(
+    stage: &str,
+    key_hex: &str,
+    image: &PathBuf,
+    aad_hex: Option<&str>,
+    armor: bool,
+    key_tag: Option<&str>,
+) -> Result<(), String> {
+    let seed: [u8; 32] = hex::decode(key_hex)
+        .map_err(|e| format!("Invalid key hex: {}", e))?
+        .try_into()
+        .map_err(|_| "Signing key seed must be 32 bytes".to_string())?;
+    let signing_key = SigningKey::from_bytes(&seed);
+
+    let signed = sign_image(&signing_key, image, aad_hex)?;
+
+    if armor {
+        let key_tag = key_tag
+            .ok_or_else(|| "--armor requires --key-tag (the trusted key name/tag to verify against)".to_string())?;
+        let attestation = ArmoredAttestation {
+            stage: stage.to_string(),
+            key_tag: key_tag.to_string(),
+            hash: signed.hash_hex,
+            sig: signed.sig_hex,
+            aad: signed.aad_hex,
+            alg: Some(Algorithm::Ed25519.to_string()),
+        };
+        println!("{}", encode_armored_attestation(&attestation)?);
+    } else {
+        println!("{}.sig:{}", stage, signed.sig_hex);
+        println!("{}.hash:{}", stage, signed.hash_hex);
+        if let Some(ref aad_hex) = signed.aad_hex {
+            println!("{}.aad_len:{}", stage, aad_hex.len() / 2);
+            println!("{}.aad:{}", stage, aad_hex);
+        }
+    }
+
+    Ok(())
 }
 
 /// A wrapper struct that implements Digest but returns a precomputed hash.
@@ -121,44 +383,131 @@ impl Reset for PrecomputedHash {
     }
 }
 
-fn resolve_pubkey(input: &str) -> Result<[u8; 32], String> {
-    // Check if it's a known key name
-    for (name, pk_hex) in PUBKEYS {
+/// Resolve a key name or raw hex string to its bytes, algorithm, display name
+/// and validity status. Consults `store` first (by name, then by raw pubkey
+/// match) and falls back to the built-in `PUBKEYS` table, then to treating
+/// `input` as raw hex with no further metadata.
+fn resolve_key(input: &str, store: Option<&TrustStore>) -> Result<ResolvedKey, String> {
+    if let Some(store) = store {
+        for entry in &store.keys {
+            if entry.name.eq_ignore_ascii_case(input) {
+                let bytes =
+                    hex::decode(&entry.pubkey).map_err(|e| format!("Invalid trust-store key '{}': {}", entry.name, e))?;
+                let alg = entry.alg.as_deref().map(Algorithm::parse).transpose()?;
+                return Ok(ResolvedKey {
+                    bytes,
+                    alg,
+                    display_name: Some(entry.name.clone()),
+                    status: key_status(entry)?,
+                });
+            }
+        }
+    }
+
+    for (name, pk_hex, alg) in PUBKEYS {
         if input.eq_ignore_ascii_case(name) {
             let bytes = hex::decode(pk_hex).map_err(|e| format!("Invalid built-in key: {}", e))?;
-            return bytes
-                .try_into()
-                .map_err(|_| "Built-in key has wrong length".to_string());
+            return Ok(ResolvedKey {
+                bytes,
+                alg: Some(*alg),
+                display_name: Some(name.to_string()),
+                status: KeyStatus::Valid,
+            });
         }
     }
 
-    // Otherwise treat as hex
+    // Otherwise treat as hex; check whether it matches a trust-store entry by
+    // value so a revoked/expired key is still caught even when passed raw.
     let bytes = hex::decode(input).map_err(|e| format!("Invalid hex: {}", e))?;
-    bytes
-        .try_into()
-        .map_err(|_| format!("Public key must be 32 bytes, got {}", input.len() / 2))
+    if let Some(store) = store {
+        for entry in &store.keys {
+            if let Ok(entry_bytes) = hex::decode(&entry.pubkey) {
+                if entry_bytes == bytes {
+                    let alg = entry.alg.as_deref().map(Algorithm::parse).transpose()?;
+                    return Ok(ResolvedKey {
+                        bytes,
+                        alg,
+                        display_name: Some(entry.name.clone()),
+                        status: key_status(entry)?,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(ResolvedKey { bytes, alg: None, display_name: None, status: KeyStatus::Valid })
 }
 
-fn identify_key(pubkey_bytes: &[u8; 32]) -> Option<&'static str> {
-    let pubkey_hex = hex::encode(pubkey_bytes);
-    for (name, pk) in PUBKEYS {
-        if pubkey_hex.eq_ignore_ascii_case(pk) {
-            return Some(name);
-        }
+/// Auto-detect the algorithm from key/signature shape when not forced
+/// explicitly: a 33-byte compressed secp256k1 public key, or a DER-encoded
+/// (and therefore non-64-byte) signature, both indicate secp256k1/ECDSA.
+fn detect_algorithm(pubkey_bytes: &[u8], sig_bytes: &[u8]) -> Algorithm {
+    if pubkey_bytes.len() == 33 {
+        return Algorithm::Secp256k1;
     }
-    None
+    if sig_bytes.first() == Some(&0x30) && sig_bytes.len() != 64 {
+        return Algorithm::Secp256k1;
+    }
+    Algorithm::Ed25519
 }
 
-fn tag_to_key_name(tag: &str) -> Option<&'static str> {
-    let tag = tag.trim();  // Handle trailing spaces in 4-byte tags
+/// Map an audit-output key tag to a key name, consulting `store` (by its
+/// optional `tag` field) before the built-in `TAG_TO_KEY` table. Also accepts
+/// a full key name directly (e.g. "developer", not just its "devl" audit
+/// tag), so callers like `sign --armor --key-tag` can name a key without
+/// knowing its short hardware tag - still restricted to known store/built-in
+/// names, never an arbitrary attacker-supplied string.
+fn tag_to_key_name(tag: &str, store: Option<&TrustStore>) -> Option<String> {
+    let tag = tag.trim(); // Handle trailing spaces in 4-byte tags
+
+    if let Some(store) = store {
+        for entry in &store.keys {
+            if let Some(ref entry_tag) = entry.tag {
+                if tag.eq_ignore_ascii_case(entry_tag) {
+                    return Some(entry.name.clone());
+                }
+            }
+            if entry.name.eq_ignore_ascii_case(tag) {
+                return Some(entry.name.clone());
+            }
+        }
+    }
+
     for (t, name) in TAG_TO_KEY {
         if tag.eq_ignore_ascii_case(t) {
-            return Some(name);
+            return Some(name.to_string());
         }
     }
+
+    for (name, _, _) in PUBKEYS {
+        if tag.eq_ignore_ascii_case(name) {
+            return Some(name.to_string());
+        }
+    }
+
     None
 }
 
+/// Map a key slot number to a key name, consulting `store` before the
+/// built-in slot convention (0=bao1, 1=bao2, 2=beta, 3=developer).
+fn slot_to_key_name(slot: u32, store: Option<&TrustStore>) -> Option<String> {
+    if let Some(store) = store {
+        for entry in &store.keys {
+            if entry.slot == Some(slot) {
+                return Some(entry.name.clone());
+            }
+        }
+    }
+
+    match slot {
+        0 => Some("bao1".to_string()),
+        1 => Some("bao2".to_string()),
+        2 => Some("beta".to_string()),
+        3 => Some("developer".to_string()),
+        _ => None,
+    }
+}
+
 /// Parsed attestation data for a single stage
 #[derive(Default, Debug)]
 struct StageData {
@@ -168,6 +517,7 @@ struct StageData {
     key_tag: Option<String>,
     aad_len: Option<u32>,
     aad: Option<String>,
+    alg: Option<String>,
 }
 
 /// Parse audit output and extract attestation data
@@ -177,7 +527,7 @@ fn parse_audit_output(input: &str) -> HashMap<String, StageData> {
     for line in input.lines() {
         let line = line.trim();
 
-        // Parse: boot0.sig:<hex>, boot0.hash:<hex>, boot0.aad_len:<num>, boot0.aad:<hex>
+        // Parse: boot0.sig:<hex>, boot0.hash:<hex>, boot0.aad_len:<num>, boot0.aad:<hex>, boot0.alg:<name>
         for stage in &["boot0", "boot1", "loader"] {
             if let Some(rest) = line.strip_prefix(&format!("{}.sig:", stage)) {
                 stages.entry(stage.to_string()).or_default().sig = Some(rest.trim().to_string());
@@ -193,6 +543,9 @@ fn parse_audit_output(input: &str) -> HashMap<String, StageData> {
             if let Some(rest) = line.strip_prefix(&format!("{}.aad:", stage)) {
                 stages.entry(stage.to_string()).or_default().aad = Some(rest.trim().to_string());
             }
+            if let Some(rest) = line.strip_prefix(&format!("{}.alg:", stage)) {
+                stages.entry(stage.to_string()).or_default().alg = Some(rest.trim().to_string());
+            }
         }
 
         // Parse: "Boot0: key 2/true (beta) -> ..."
@@ -238,22 +591,33 @@ fn parse_key_line(line: &str, prefix: &str) -> Option<(u32, String)> {
     Some((slot, tag))
 }
 
-/// Verify a signature - supports both Ed25519ph and FIDO2 modes
+/// Verify a signature - supports both Ed25519ph and FIDO2 modes, and both
+/// Ed25519 and secp256k1/ECDSA keys.
 ///
-/// - If aad is None or empty: Ed25519ph mode (verify_prehashed)
-/// - If aad is Some with data: FIDO2 mode (standard Ed25519 over aad || SHA256(hash))
+/// - If aad is None or empty: Ed25519ph mode (verify_prehashed) for Ed25519
+///   keys, or ECDSA over SHA256(hash) for secp256k1 keys
+/// - If aad is Some with data: FIDO2 mode (standard Ed25519, or the secp256k1
+///   ECDSA equivalent, over aad || SHA256(hash))
+/// - `forced_alg` overrides auto-detection from key/signature shape; falls
+///   back to the resolved key's algorithm (trust store, then built-in table),
+///   then to `detect_algorithm`
+/// - `store`, if given, is consulted before the built-in key table and can
+///   mark a key revoked or expired, in which case verification fails with
+///   `Err("REVOKED")`/`Err("EXPIRED")` before any cryptographic check runs
 fn verify_single(
     pubkey_hex: &str,
     hash_hex: &str,
     sig_hex: &str,
     aad_hex: Option<&str>,
     name: &str,
+    forced_alg: Option<Algorithm>,
+    store: Option<&TrustStore>,
 ) -> Result<(), String> {
-    use ed25519_dalek::Verifier;
-    use sha2::{Sha256, Digest};
-
-    // Parse public key
-    let pubkey_bytes = resolve_pubkey(pubkey_hex)?;
+    // Parse public key and signature as raw bytes; length/shape and the
+    // resolved key's algorithm (if any) determine the algorithm below.
+    let resolved = resolve_key(pubkey_hex, store)?;
+    let pubkey_bytes = resolved.bytes;
+    let sig_bytes = hex::decode(sig_hex).map_err(|e| format!("Invalid signature hex: {}", e))?;
 
     // Parse hash (SHA-512 of signed region)
     let hash_bytes: [u8; 64] = hex::decode(hash_hex)
@@ -261,12 +625,6 @@ fn verify_single(
         .try_into()
         .map_err(|_| format!("Hash must be 64 bytes, got {}", hash_hex.len() / 2))?;
 
-    // Parse signature
-    let sig_bytes: [u8; 64] = hex::decode(sig_hex)
-        .map_err(|e| format!("Invalid signature hex: {}", e))?
-        .try_into()
-        .map_err(|_| format!("Signature must be 64 bytes, got {}", sig_hex.len() / 2))?;
-
     // Parse AAD if provided
     let aad_bytes: Option<Vec<u8>> = match aad_hex {
         Some(hex) if !hex.is_empty() => {
@@ -275,14 +633,19 @@ fn verify_single(
         _ => None,
     };
 
+    let alg = forced_alg
+        .or(resolved.alg)
+        .unwrap_or_else(|| detect_algorithm(&pubkey_bytes, &sig_bytes));
+
     // Determine verification mode
     let is_fido2 = aad_bytes.is_some();
 
     // Display verification info
     println!("=== Verifying {} ===", name);
+    println!("Algorithm:  {}", alg);
     println!("Mode:       {}", if is_fido2 { "FIDO2" } else { "Ed25519ph" });
 
-    if let Some(key_name) = identify_key(&pubkey_bytes) {
+    if let Some(ref key_name) = resolved.display_name {
         println!("Public key: {}", key_name);
     } else {
         println!(
@@ -292,6 +655,20 @@ fn verify_single(
         );
     }
 
+    // A revoked or expired key fails verification outright, even if the
+    // signature is mathematically valid.
+    match resolved.status {
+        KeyStatus::Revoked => {
+            println!("✗ REVOKED: key is no longer trusted\n");
+            return Err("REVOKED".to_string());
+        }
+        KeyStatus::Expired => {
+            println!("✗ EXPIRED: key validity period has ended\n");
+            return Err("EXPIRED".to_string());
+        }
+        KeyStatus::Valid => {}
+    }
+
     println!(
         "Hash:       {}...{}",
         &hash_hex[..16.min(hash_hex.len())],
@@ -306,32 +683,33 @@ fn verify_single(
         println!("AAD:        {} bytes", aad.len());
     }
 
-    // Create verification key
-    let verifying_key =
-        VerifyingKey::from_bytes(&pubkey_bytes).map_err(|e| format!("Invalid public key: {}", e))?;
+    let result = match alg {
+        Algorithm::Secp256k1 => verify_secp256k1(&pubkey_bytes, &hash_bytes, &sig_bytes, aad_bytes.as_deref()),
+        Algorithm::Ed25519 => {
+            use ed25519_dalek::Verifier;
 
-    // Create signature
-    let signature = Signature::from_bytes(&sig_bytes);
-
-    let result = if is_fido2 {
-        // FIDO2 mode: verify standard Ed25519 over (aad || SHA256(SHA512_hash))
-        // 1. hash_bytes is already SHA-512 of the signed region
-        // 2. Compute SHA-256 of that
-        let mut sha256 = Sha256::new();
-        Digest::update(&mut sha256, &hash_bytes);
-        let hashed_hash = sha256.finalize();
+            let pubkey_fixed: [u8; 32] = pubkey_bytes
+                .try_into()
+                .map_err(|_| "Ed25519 public key must be 32 bytes".to_string())?;
+            let sig_fixed: [u8; 64] = sig_bytes
+                .try_into()
+                .map_err(|_| "Ed25519 signature must be 64 bytes".to_string())?;
 
-        // 3. Concatenate: aad || SHA256(SHA512(image))
-        let mut msg = Vec::new();
-        msg.extend_from_slice(aad_bytes.as_ref().unwrap());
-        msg.extend_from_slice(&hashed_hash);
+            let verifying_key = VerifyingKey::from_bytes(&pubkey_fixed)
+                .map_err(|e| format!("Invalid public key: {}", e))?;
+            let signature = Signature::from_bytes(&sig_fixed);
 
-        // 4. Standard Ed25519 verify
-        verifying_key.verify(&msg, &signature)
-    } else {
-        // Ed25519ph mode: verify_prehashed with the SHA-512 hash
-        let prehash = PrecomputedHash { hash: hash_bytes };
-        verifying_key.verify_prehashed(prehash, None, &signature)
+            let ed25519_result = if is_fido2 {
+                // FIDO2 mode: verify standard Ed25519 over (aad || SHA256(SHA512_hash))
+                let msg = fido2_message(&hash_bytes, aad_bytes.as_ref().unwrap());
+                verifying_key.verify(&msg, &signature)
+            } else {
+                // Ed25519ph mode: verify_prehashed with the SHA-512 hash
+                let prehash = PrecomputedHash { hash: hash_bytes };
+                verifying_key.verify_prehashed(prehash, None, &signature)
+            };
+            ed25519_result.map_err(|e| e.to_string())
+        }
     };
 
     match result {
@@ -346,17 +724,368 @@ fn verify_single(
     }
 }
 
+/// Verify a secp256k1/ECDSA attestation signature.
+///
+/// Builds the signed digest from the stage's SHA-512 hash: `sha256(hash)`
+/// for non-FIDO2 stages, or `sha256(aad || sha256(hash))` (a hash of the
+/// Ed25519 FIDO2 message) in FIDO2 mode, since ECDSA signs a fixed 32-byte
+/// digest rather than an arbitrary-length message. Accepts either compact or
+/// DER-encoded signatures.
+fn verify_secp256k1(
+    pubkey_bytes: &[u8],
+    hash_bytes: &[u8; 64],
+    sig_bytes: &[u8],
+    aad_bytes: Option<&[u8]>,
+) -> Result<(), String> {
+    use secp256k1::ecdsa::Signature as EcdsaSignature;
+    use secp256k1::{Message, PublicKey, Secp256k1};
+    use sha2::{Digest, Sha256};
+
+    let public_key =
+        PublicKey::from_slice(pubkey_bytes).map_err(|e| format!("Invalid secp256k1 public key: {}", e))?;
+
+    let mut sha256 = Sha256::new();
+    Digest::update(&mut sha256, hash_bytes);
+    let hashed_hash = sha256.finalize();
+
+    let digest: [u8; 32] = match aad_bytes {
+        Some(aad) => {
+            let msg = fido2_message(hash_bytes, aad);
+            let mut outer = Sha256::new();
+            Digest::update(&mut outer, &msg);
+            outer.finalize().into()
+        }
+        None => hashed_hash.into(),
+    };
+
+    let message = Message::from_digest(digest);
+
+    // Same shape rule as `detect_algorithm`: a 64-byte signature is compact
+    // even when its first byte happens to be 0x30.
+    let signature = if sig_bytes.len() != 64 && sig_bytes.first() == Some(&0x30) {
+        EcdsaSignature::from_der(sig_bytes).map_err(|e| format!("Invalid DER signature: {}", e))?
+    } else {
+        EcdsaSignature::from_compact(sig_bytes).map_err(|e| format!("Invalid compact signature: {}", e))?
+    };
+
+    let secp = Secp256k1::verification_only();
+    secp.verify_ecdsa(&message, &signature, &public_key)
+        .map_err(|e| format!("secp256k1 verification failed: {}", e))
+}
+
+/// A single stage's worth of attestation data, self-describing enough to
+/// decode straight into a `StageData` without manual hex extraction.
+///
+/// Carries `key_tag` rather than a raw public key: the key is always resolved
+/// through the trust store / built-in table from this tag, exactly like the
+/// plain audit format's `key N (tag)` line, so an armored block can't make
+/// the verifier trust an arbitrary embedded key.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ArmoredAttestation {
+    stage: String,
+    key_tag: String,
+    hash: String,
+    sig: String,
+    aad: Option<String>,
+    alg: Option<String>,
+}
+
+const ARMOR_LABEL: &str = "BAOCHIP ATTESTATION";
+
+/// CRC-24 checksum as specified by RFC 4880 (OpenPGP), used the same way here
+/// to guard an armored block against mangling by email/chat.
+fn crc24(data: &[u8]) -> u32 {
+    const CRC24_INIT: u32 = 0x00B7_04CE;
+    const CRC24_POLY: u32 = 0x0186_4CFB;
+
+    let mut crc = CRC24_INIT;
+    for &byte in data {
+        crc ^= (byte as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & 0x00FF_FFFF
+}
+
+/// Wrap `data` in a `-----BEGIN <label>-----` / `-----END <label>-----` block:
+/// a base64 body wrapped at 64 columns, followed by a `=`-prefixed base64
+/// CRC-24 checksum line, in the same shape as OpenPGP ASCII armor.
+fn encode_armor(label: &str, data: &[u8]) -> String {
+    use base64::Engine;
+
+    let mut out = format!("-----BEGIN {}-----\n\n", label);
+
+    let body = base64::engine::general_purpose::STANDARD.encode(data);
+    for line in body.as_bytes().chunks(64) {
+        out.push_str(std::str::from_utf8(line).expect("base64 output is ASCII"));
+        out.push('\n');
+    }
+
+    let crc = crc24(data);
+    let crc_bytes = [(crc >> 16) as u8, (crc >> 8) as u8, crc as u8];
+    out.push('=');
+    out.push_str(&base64::engine::general_purpose::STANDARD.encode(crc_bytes));
+    out.push('\n');
+
+    out.push_str(&format!("-----END {}-----\n", label));
+    out
+}
+
+/// Reverse of `encode_armor`: extract and validate one `label`-delimited
+/// block from `input`, returning its decoded body bytes.
+fn decode_armor(input: &str, label: &str) -> Result<Vec<u8>, String> {
+    use base64::Engine;
+
+    let begin = format!("-----BEGIN {}-----", label);
+    let end = format!("-----END {}-----", label);
+
+    let start = input.find(&begin).ok_or_else(|| format!("Missing '{}' header", begin))?;
+    let rest = &input[start + begin.len()..];
+    let stop = rest.find(&end).ok_or_else(|| format!("Missing '{}' footer", end))?;
+    let body = &rest[..stop];
+
+    let mut b64_lines = Vec::new();
+    let mut checksum_line = None;
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match line.strip_prefix('=') {
+            Some(crc) => checksum_line = Some(crc.to_string()),
+            None => b64_lines.push(line),
+        }
+    }
+
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(b64_lines.concat())
+        .map_err(|e| format!("Invalid armored body: {}", e))?;
+
+    let checksum_b64 = checksum_line.ok_or("Missing armor checksum line")?;
+    let checksum_bytes = base64::engine::general_purpose::STANDARD
+        .decode(&checksum_b64)
+        .map_err(|e| format!("Invalid armor checksum: {}", e))?;
+    let expected: [u8; 3] = checksum_bytes
+        .try_into()
+        .map_err(|_| "Armor checksum must be 3 bytes".to_string())?;
+    let expected = ((expected[0] as u32) << 16) | ((expected[1] as u32) << 8) | expected[2] as u32;
+
+    let actual = crc24(&data);
+    if actual != expected {
+        return Err(format!("Armor checksum mismatch: expected {:06x}, got {:06x}", expected, actual));
+    }
+
+    Ok(data)
+}
+
+fn encode_armored_attestation(attestation: &ArmoredAttestation) -> Result<String, String> {
+    let json = serde_json::to_vec(attestation).map_err(|e| format!("Failed to encode attestation: {}", e))?;
+    Ok(encode_armor(ARMOR_LABEL, &json))
+}
+
+/// Decode one armored attestation block into the stage name it describes and
+/// its `StageData`, ready to merge into the same map `parse_audit_output` builds.
+fn decode_armored_attestation(input: &str) -> Result<(String, StageData), String> {
+    let data = decode_armor(input, ARMOR_LABEL)?;
+    let attestation: ArmoredAttestation =
+        serde_json::from_slice(&data).map_err(|e| format!("Invalid attestation payload: {}", e))?;
+
+    let stage = StageData {
+        sig: Some(attestation.sig),
+        hash: Some(attestation.hash),
+        key_slot: None,
+        key_tag: Some(attestation.key_tag),
+        aad_len: attestation.aad.as_ref().map(|a| a.len() as u32 / 2),
+        aad: attestation.aad,
+        alg: attestation.alg,
+    };
+
+    Ok((attestation.stage, stage))
+}
+
+/// Decode every armored attestation block in `input` (one or more, concatenated)
+/// into the same `HashMap<String, StageData>` shape `parse_audit_output` builds.
+fn parse_armored_input(input: &str) -> Result<HashMap<String, StageData>, String> {
+    let mut stages = HashMap::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("-----BEGIN") {
+        let block = &rest[start..];
+        let (stage_name, stage_data) = decode_armored_attestation(block)?;
+        let end = block.find(&format!("-----END {}-----", ARMOR_LABEL)).unwrap_or(block.len());
+        stages.insert(stage_name, stage_data);
+        rest = &block[end..];
+    }
+
+    Ok(stages)
+}
+
+/// A single FIDO2-mode (standard Ed25519) stage ready for batch verification.
+struct BatchEntry<'a> {
+    name: &'a str,
+    pubkey_hex: String,
+    hash_hex: String,
+    sig_hex: String,
+    aad_hex: String,
+    /// The algorithm this stage resolved to before joining the batch, so a
+    /// batch-failure fallback can re-check it as itself instead of forcing
+    /// Ed25519 on every stage in the failed batch.
+    alg: Option<Algorithm>,
+}
+
+/// A single stage queued for individual (non-batch) verification via
+/// `verify_single`: either a prehashed (Ed25519ph) stage, or a FIDO2 stage
+/// that couldn't join the batch (non-Ed25519, or a revoked/expired key).
+struct PendingEntry<'a> {
+    name: &'a str,
+    pubkey_hex: String,
+    hash_hex: String,
+    sig_hex: String,
+    aad_hex: Option<String>,
+    alg: Option<Algorithm>,
+}
+
+/// Build the FIDO2 message `aad || SHA256(SHA512_hash)` for one stage, as used
+/// both by `verify_single` and the batch equation below.
+fn fido2_message(hash_bytes: &[u8; 64], aad_bytes: &[u8]) -> Vec<u8> {
+    let mut sha256 = Sha256::new();
+    Digest::update(&mut sha256, hash_bytes);
+    let hashed_hash = sha256.finalize();
+
+    let mut msg = Vec::with_capacity(aad_bytes.len() + hashed_hash.len());
+    msg.extend_from_slice(aad_bytes);
+    msg.extend_from_slice(&hashed_hash);
+    msg
+}
+
+/// Batch-verify all FIDO2-mode stages with a single multiscalar equation via
+/// `ed25519_dalek::verify_batch`: for each signature `(R_i, s_i)` it recovers
+/// the per-signature challenge `k_i = H(R_i || A_i || M_i)`, draws a random
+/// 128-bit scalar `z_i`, and accepts iff
+/// `(-Σ z_i·s_i)·B + Σ z_i·R_i + Σ (z_i·k_i)·A_i = 0`.
+///
+/// There is no batch form for the prehashed (Ed25519ph) path, so that one is
+/// still checked stage-by-stage via `verify_single`.
+fn verify_batch_fido2(entries: &[BatchEntry], store: Option<&TrustStore>) -> Result<(), String> {
+    let mut messages: Vec<Vec<u8>> = Vec::with_capacity(entries.len());
+    let mut signatures: Vec<Signature> = Vec::with_capacity(entries.len());
+    let mut verifying_keys: Vec<VerifyingKey> = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let resolved = resolve_key(&entry.pubkey_hex, store)?;
+        let pubkey_bytes: [u8; 32] = resolved
+            .bytes
+            .try_into()
+            .map_err(|_| format!("Ed25519 public key must be 32 bytes for {}", entry.name))?;
+        let verifying_key = VerifyingKey::from_bytes(&pubkey_bytes)
+            .map_err(|e| format!("Invalid public key for {}: {}", entry.name, e))?;
+
+        let hash_bytes: [u8; 64] = hex::decode(&entry.hash_hex)
+            .map_err(|e| format!("Invalid hash hex for {}: {}", entry.name, e))?
+            .try_into()
+            .map_err(|_| format!("Hash must be 64 bytes for {}", entry.name))?;
+
+        let sig_bytes: [u8; 64] = hex::decode(&entry.sig_hex)
+            .map_err(|e| format!("Invalid signature hex for {}: {}", entry.name, e))?
+            .try_into()
+            .map_err(|_| format!("Signature must be 64 bytes for {}", entry.name))?;
+
+        let aad_bytes =
+            hex::decode(&entry.aad_hex).map_err(|e| format!("Invalid AAD hex for {}: {}", entry.name, e))?;
+
+        messages.push(fido2_message(&hash_bytes, &aad_bytes));
+        signatures.push(Signature::from_bytes(&sig_bytes));
+        verifying_keys.push(verifying_key);
+    }
+
+    let message_refs: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+
+    ed25519_dalek::verify_batch(&message_refs, &signatures, &verifying_keys)
+        .map_err(|e| format!("batch verification failed: {}", e))
+}
+
+/// Outcome of verifying one stage, distinguishing a bad signature from a
+/// key that failed the trust store's revocation/expiry check.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VerifyOutcome {
+    Verified,
+    Failed,
+    Revoked,
+    Expired,
+}
+
+impl VerifyOutcome {
+    fn from_result(result: &Result<(), String>) -> Self {
+        match result {
+            Ok(()) => VerifyOutcome::Verified,
+            Err(e) if e == "REVOKED" => VerifyOutcome::Revoked,
+            Err(e) if e == "EXPIRED" => VerifyOutcome::Expired,
+            Err(_) => VerifyOutcome::Failed,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            VerifyOutcome::Verified => "✓ VERIFIED",
+            VerifyOutcome::Failed => "✗ FAILED",
+            VerifyOutcome::Revoked => "✗ REVOKED",
+            VerifyOutcome::Expired => "✗ EXPIRED",
+        }
+    }
+}
+
 fn main() {
     let args = Args::parse();
 
+    match &args.command {
+        Some(Commands::Keygen) => {
+            keygen();
+            return;
+        }
+        Some(Commands::Sign { stage, key, image, aad, armor, key_tag }) => {
+            let result = sign(stage, key, image, aad.as_deref(), *armor, key_tag.as_deref());
+            if let Err(e) = &result {
+                eprintln!("Error: {}", e);
+            }
+            std::process::exit(if result.is_ok() { 0 } else { 1 });
+        }
+        None => {}
+    }
+
+    let trust_store = match &args.trust_store {
+        Some(path) => match load_trust_store(path) {
+            Ok(store) => Some(store),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let trust_store = trust_store.as_ref();
+
     // If explicit values provided, verify directly
-    if args.pubkey.is_some() && args.hash.is_some() && args.sig.is_some() {
+    if let (Some(pubkey), Some(hash), Some(sig)) = (&args.pubkey, &args.hash, &args.sig) {
+        let forced_alg = match args.alg.as_deref().map(Algorithm::parse) {
+            Some(Ok(alg)) => Some(alg),
+            Some(Err(e)) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+            None => None,
+        };
         let result = verify_single(
-            args.pubkey.as_ref().unwrap(),
-            args.hash.as_ref().unwrap(),
-            args.sig.as_ref().unwrap(),
+            pubkey,
+            hash,
+            sig,
             args.aad.as_deref(),  // Use AAD if provided for FIDO2 mode
             &args.name,
+            forced_alg,
+            trust_store,
         );
         match &result {
             Ok(_) => {}
@@ -367,7 +1096,7 @@ fn main() {
 
     // Otherwise, read audit output from stdin
     let stdin = io::stdin();
-    let input: String = stdin.lock().lines().filter_map(|l| l.ok()).collect::<Vec<_>>().join("\n");
+    let input: String = stdin.lock().lines().map_while(Result::ok).collect::<Vec<_>>().join("\n");
 
     if input.is_empty() {
         eprintln!("No input provided.");
@@ -376,7 +1105,17 @@ fn main() {
         std::process::exit(1);
     }
 
-    let stages = parse_audit_output(&input);
+    let stages = if input.contains("-----BEGIN") {
+        match parse_armored_input(&input) {
+            Ok(stages) => stages,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        parse_audit_output(&input)
+    };
 
     if stages.is_empty() {
         eprintln!("No attestation data found in input.");
@@ -393,7 +1132,9 @@ fn main() {
 
     println!("Found attestation data for: {:?}\n", stages.keys().collect::<Vec<_>>());
 
-    let mut results: Vec<(&str, bool)> = Vec::new();
+    let mut results: Vec<(&str, VerifyOutcome)> = Vec::new();
+    let mut prehashed: Vec<PendingEntry> = Vec::new();
+    let mut fido2: Vec<BatchEntry> = Vec::new();
 
     for stage_name in stages_to_verify {
         if let Some(stage) = stages.get(stage_name) {
@@ -405,24 +1146,23 @@ fn main() {
                 }
             };
 
-            // Determine which key to use
+            // Determine which key to use: trust store / built-in defaults by
+            // tag (from the plain audit format or an armored block's
+            // `key_tag`) or slot - never a key an attacker-controlled input
+            // carries directly.
             let pubkey = if let Some(ref tag) = stage.key_tag {
-                if let Some(key_name) = tag_to_key_name(tag) {
-                    key_name.to_string()
+                if let Some(key_name) = tag_to_key_name(tag, trust_store) {
+                    key_name
                 } else {
                     eprintln!("Unknown key tag '{}' for {}", tag, stage_name);
                     continue;
                 }
             } else if let Some(slot) = stage.key_slot {
-                match slot {
-                    0 => "bao1".to_string(),
-                    1 => "bao2".to_string(),
-                    2 => "beta".to_string(),
-                    3 => "developer".to_string(),
-                    _ => {
-                        eprintln!("Unknown key slot {} for {}", slot, stage_name);
-                        continue;
-                    }
+                if let Some(key_name) = slot_to_key_name(slot, trust_store) {
+                    key_name
+                } else {
+                    eprintln!("Unknown key slot {} for {}", slot, stage_name);
+                    continue;
                 }
             } else {
                 // Default to developer key
@@ -430,24 +1170,355 @@ fn main() {
                 "developer".to_string()
             };
 
-            // Get AAD if present (for FIDO2 mode)
-            let aad = stage.aad.as_deref();
+            let stage_alg = match stage.alg.as_deref().map(Algorithm::parse) {
+                Some(Ok(alg)) => Some(alg),
+                Some(Err(e)) => {
+                    eprintln!("{} for {}", e, stage_name);
+                    continue;
+                }
+                None => None,
+            };
 
-            let result = verify_single(&pubkey, hash, sig, aad, stage_name);
-            results.push((stage_name, result.is_ok()));
+            // A revoked/expired key, or a non-Ed25519 one, has no batch form
+            // here - route it through verify_single individually so the
+            // summary can report it precisely, regardless of whether the
+            // stage is otherwise FIDO2. When neither the audit line nor the
+            // trust store names an algorithm, fall back to the same
+            // shape-based detection the single-stage path uses instead of
+            // assuming Ed25519, so an undeclared secp256k1 key doesn't get
+            // forced into the Ed25519 batch and reported as a false FAILED.
+            let (resolved_alg, can_batch) = match resolve_key(&pubkey, trust_store) {
+                Ok(resolved) => {
+                    let sig_bytes = hex::decode(sig).unwrap_or_default();
+                    let alg = stage_alg
+                        .or(resolved.alg)
+                        .unwrap_or_else(|| detect_algorithm(&resolved.bytes, &sig_bytes));
+                    (alg, resolved.status == KeyStatus::Valid && alg == Algorithm::Ed25519)
+                }
+                Err(_) => (stage_alg.unwrap_or(Algorithm::Ed25519), true),
+            };
+
+            // Stages with AAD use FIDO2 mode and have a batch form, but only
+            // for Ed25519 - secp256k1 (and revoked/expired keys) have no
+            // batch equation here, so those stages are checked individually.
+            match (stage.aad.as_deref(), stage_alg) {
+                (Some(aad), _) if !aad.is_empty() && can_batch => fido2.push(BatchEntry {
+                    name: stage_name,
+                    pubkey_hex: pubkey,
+                    hash_hex: hash.clone(),
+                    sig_hex: sig.clone(),
+                    aad_hex: aad.to_string(),
+                    alg: Some(resolved_alg),
+                }),
+                (Some(aad), _) if !aad.is_empty() => prehashed.push(PendingEntry {
+                    name: stage_name,
+                    pubkey_hex: pubkey,
+                    hash_hex: hash.clone(),
+                    sig_hex: sig.clone(),
+                    aad_hex: Some(aad.to_string()),
+                    alg: stage_alg,
+                }),
+                _ => prehashed.push(PendingEntry {
+                    name: stage_name,
+                    pubkey_hex: pubkey,
+                    hash_hex: hash.clone(),
+                    sig_hex: sig.clone(),
+                    aad_hex: None,
+                    alg: stage_alg,
+                }),
+            }
+        }
+    }
+
+    for entry in &prehashed {
+        let result = verify_single(
+            &entry.pubkey_hex,
+            &entry.hash_hex,
+            &entry.sig_hex,
+            entry.aad_hex.as_deref(),
+            entry.name,
+            entry.alg,
+            trust_store,
+        );
+        results.push((entry.name, VerifyOutcome::from_result(&result)));
+    }
+
+    if !fido2.is_empty() {
+        println!("=== Batch-verifying {} FIDO2 stage(s) ===", fido2.len());
+        match verify_batch_fido2(&fido2, trust_store) {
+            Ok(()) => {
+                println!("✓ BATCH PASSED\n");
+                for entry in &fido2 {
+                    results.push((entry.name, VerifyOutcome::Verified));
+                }
+            }
+            Err(e) => {
+                println!("✗ BATCH FAILED: {} -- falling back to per-stage verification\n", e);
+                for entry in &fido2 {
+                    let result = verify_single(
+                        &entry.pubkey_hex,
+                        &entry.hash_hex,
+                        &entry.sig_hex,
+                        Some(&entry.aad_hex),
+                        entry.name,
+                        entry.alg,
+                        trust_store,
+                    );
+                    results.push((entry.name, VerifyOutcome::from_result(&result)));
+                }
+            }
         }
     }
 
     // Print summary
     println!("=== Summary ===");
     let mut all_passed = true;
-    for (stage, passed) in &results {
-        let status = if *passed { "✓ VERIFIED" } else { "✗ FAILED" };
-        println!("{}: {}", stage, status);
-        if !passed {
+    for (stage, outcome) in &results {
+        println!("{}: {}", stage, outcome.label());
+        if *outcome != VerifyOutcome::Verified {
             all_passed = false;
         }
     }
 
     std::process::exit(if all_passed { 0 } else { 1 });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Write `bytes` to a fresh temp file and return its path; callers remove
+    /// it once done. Unique per call so parallel tests don't collide.
+    fn write_temp_image(bytes: &[u8], label: &str) -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("verify_ed25519ph_test_{}_{}_{}.bin", std::process::id(), label, n));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    /// keygen -> sign -> verify_single should round-trip on a freshly
+    /// generated key and image, with no reliance on the built-in `PUBKEYS`.
+    #[test]
+    fn keygen_sign_verify_round_trip() {
+        let mut csprng = rand_core::OsRng;
+        let signing_key = SigningKey::generate(&mut csprng);
+        let pubkey_hex = hex::encode(signing_key.verifying_key().to_bytes());
+
+        let image_path = write_temp_image(b"round trip test image", "ed25519");
+        let signed = sign_image(&signing_key, &image_path, None);
+        std::fs::remove_file(&image_path).ok();
+        let signed = signed.expect("signing should succeed");
+
+        verify_single(&pubkey_hex, &signed.hash_hex, &signed.sig_hex, None, "test", None, None)
+            .expect("freshly produced signature should verify");
+    }
+
+    /// A secp256k1/ECDSA signature over the same hash/digest shape
+    /// `verify_secp256k1` expects should verify with `--alg secp256k1`.
+    #[test]
+    fn secp256k1_round_trip() {
+        use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+        let secp = Secp256k1::new();
+        let secret_key = SecretKey::from_slice(&[0x42; 32]).unwrap();
+        let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+        let pubkey_hex = hex::encode(public_key.serialize());
+
+        let mut hasher = Sha512::new();
+        Digest::update(&mut hasher, b"secp256k1 round trip test image");
+        let hash_bytes: [u8; 64] = hasher.finalize().into();
+
+        let mut sha256 = Sha256::new();
+        Digest::update(&mut sha256, hash_bytes);
+        let digest: [u8; 32] = sha256.finalize().into();
+        let signature = secp.sign_ecdsa(&Message::from_digest(digest), &secret_key);
+        let sig_hex = hex::encode(signature.serialize_compact());
+
+        verify_single(
+            &pubkey_hex,
+            &hex::encode(hash_bytes),
+            &sig_hex,
+            None,
+            "test",
+            Some(Algorithm::Secp256k1),
+            None,
+        )
+        .expect("freshly produced secp256k1 signature should verify");
+    }
+
+    /// A trust-store key marked `revoked` or past its `not_after` date must
+    /// fail verification even though the signature itself is valid.
+    #[test]
+    fn trust_store_revocation_and_expiry_override_a_valid_signature() {
+        let mut csprng = rand_core::OsRng;
+        let revoked_key = SigningKey::generate(&mut csprng);
+        let expired_key = SigningKey::generate(&mut csprng);
+
+        let store_json = format!(
+            r#"{{"keys":[
+                {{"name":"revoked-key","pubkey":"{}","revoked":true}},
+                {{"name":"expired-key","pubkey":"{}","not_after":"2000-01-01"}}
+            ]}}"#,
+            hex::encode(revoked_key.verifying_key().to_bytes()),
+            hex::encode(expired_key.verifying_key().to_bytes()),
+        );
+        let store: TrustStore = serde_json::from_str(&store_json).unwrap();
+
+        let image_path = write_temp_image(b"revocation test image", "revocation");
+        let signed_revoked = sign_image(&revoked_key, &image_path, None).unwrap();
+        let signed_expired = sign_image(&expired_key, &image_path, None).unwrap();
+        std::fs::remove_file(&image_path).ok();
+
+        assert_eq!(
+            verify_single(
+                "revoked-key",
+                &signed_revoked.hash_hex,
+                &signed_revoked.sig_hex,
+                None,
+                "test",
+                None,
+                Some(&store)
+            ),
+            Err("REVOKED".to_string())
+        );
+        assert_eq!(
+            verify_single(
+                "expired-key",
+                &signed_expired.hash_hex,
+                &signed_expired.sig_hex,
+                None,
+                "test",
+                None,
+                Some(&store)
+            ),
+            Err("EXPIRED".to_string())
+        );
+    }
+
+    /// A malformed `not_after` must surface as an error, not silently resolve
+    /// the key as valid (a trust-store typo should not disable expiry).
+    #[test]
+    fn malformed_not_after_errors_instead_of_silently_valid() {
+        let store_json = r#"{"keys":[{"name":"bad-date","pubkey":"00","not_after":"not-a-date"}]}"#;
+        let store: TrustStore = serde_json::from_str(store_json).unwrap();
+
+        let result = resolve_key("bad-date", Some(&store));
+        assert!(result.is_err(), "malformed not_after must surface an error, not silently resolve as valid");
+    }
+
+    /// `verify_batch_fido2` should accept a batch of valid signatures, reject
+    /// one containing a corrupted signature, and the per-stage fallback
+    /// (what `main` does on batch failure) should then pin down which stage.
+    #[test]
+    fn batch_verify_passes_then_pinpoints_a_bad_signature() {
+        let mut csprng = rand_core::OsRng;
+        let key_a = SigningKey::generate(&mut csprng);
+        let key_b = SigningKey::generate(&mut csprng);
+        let aad_hex = hex::encode(b"test-aad");
+
+        let image_path = write_temp_image(b"batch test image", "batch");
+        let signed_a = sign_image(&key_a, &image_path, Some(&aad_hex)).unwrap();
+        let signed_b = sign_image(&key_b, &image_path, Some(&aad_hex)).unwrap();
+        std::fs::remove_file(&image_path).ok();
+
+        let good_entries = vec![
+            BatchEntry {
+                name: "a",
+                pubkey_hex: hex::encode(key_a.verifying_key().to_bytes()),
+                hash_hex: signed_a.hash_hex.clone(),
+                sig_hex: signed_a.sig_hex.clone(),
+                aad_hex: aad_hex.clone(),
+                alg: Some(Algorithm::Ed25519),
+            },
+            BatchEntry {
+                name: "b",
+                pubkey_hex: hex::encode(key_b.verifying_key().to_bytes()),
+                hash_hex: signed_b.hash_hex.clone(),
+                sig_hex: signed_b.sig_hex.clone(),
+                aad_hex: aad_hex.clone(),
+                alg: Some(Algorithm::Ed25519),
+            },
+        ];
+        verify_batch_fido2(&good_entries, None).expect("batch of valid signatures should pass");
+
+        let mut bad_sig_bytes = hex::decode(&signed_b.sig_hex).unwrap();
+        bad_sig_bytes[0] ^= 0xff;
+        let bad_entries = vec![
+            BatchEntry {
+                name: "a",
+                pubkey_hex: hex::encode(key_a.verifying_key().to_bytes()),
+                hash_hex: signed_a.hash_hex.clone(),
+                sig_hex: signed_a.sig_hex.clone(),
+                aad_hex: aad_hex.clone(),
+                alg: Some(Algorithm::Ed25519),
+            },
+            BatchEntry {
+                name: "b",
+                pubkey_hex: hex::encode(key_b.verifying_key().to_bytes()),
+                hash_hex: signed_b.hash_hex.clone(),
+                sig_hex: hex::encode(bad_sig_bytes),
+                aad_hex: aad_hex.clone(),
+                alg: Some(Algorithm::Ed25519),
+            },
+        ];
+        assert!(verify_batch_fido2(&bad_entries, None).is_err(), "a corrupted signature must fail the batch");
+
+        // Per-stage fallback must identify exactly which stage is bad.
+        assert!(verify_single(
+            &bad_entries[0].pubkey_hex,
+            &bad_entries[0].hash_hex,
+            &bad_entries[0].sig_hex,
+            Some(&bad_entries[0].aad_hex),
+            "a",
+            bad_entries[0].alg,
+            None
+        )
+        .is_ok());
+        assert!(verify_single(
+            &bad_entries[1].pubkey_hex,
+            &bad_entries[1].hash_hex,
+            &bad_entries[1].sig_hex,
+            Some(&bad_entries[1].aad_hex),
+            "b",
+            bad_entries[1].alg,
+            None
+        )
+        .is_err());
+    }
+
+    /// An armored attestation block should encode and decode back to the
+    /// same stage data, and a corrupted block should fail its checksum.
+    #[test]
+    fn armor_encode_decode_round_trip() {
+        let attestation = ArmoredAttestation {
+            stage: "boot1".to_string(),
+            key_tag: "developer".to_string(),
+            hash: "aa".repeat(64),
+            sig: "bb".repeat(64),
+            aad: Some("cc".to_string()),
+            alg: Some("ed25519".to_string()),
+        };
+
+        let armored = encode_armored_attestation(&attestation).unwrap();
+        let (stage_name, stage_data) = decode_armored_attestation(&armored).unwrap();
+
+        assert_eq!(stage_name, "boot1");
+        assert_eq!(stage_data.key_tag.as_deref(), Some("developer"));
+        assert_eq!(stage_data.hash.as_deref(), Some(attestation.hash.as_str()));
+        assert_eq!(stage_data.sig.as_deref(), Some(attestation.sig.as_str()));
+        assert_eq!(stage_data.aad.as_deref(), Some("cc"));
+
+        // Corrupting the body must be caught by the CRC-24 checksum. The
+        // first body character follows the blank line after the header.
+        let body_start = armored.find("\n\n").unwrap() + 2;
+        let replacement = if armored.as_bytes()[body_start] == b'A' { "B" } else { "A" };
+        let mut corrupted = armored.clone();
+        corrupted.replace_range(body_start..body_start + 1, replacement);
+        assert!(
+            matches!(decode_armored_attestation(&corrupted), Err(e) if e.contains("checksum")),
+            "a corrupted armored block must fail its checksum"
+        );
+    }
+}